@@ -1,9 +1,17 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use pq_macsec::{
     init_logging,
-    link::{Link, config::LinkConfig},
-    nodes::simple::SimpleNode, simulator::{Simulator, topology::p2p::P2PConnection},
+    link::{Link, config::{CorruptionModel, GilbertElliott, LinkConfig}},
+    nodes::simple::SimpleNode, simulator::{SimTime, Simulator, topology::p2p::P2PConnection},
 };
+use tracing::info;
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Corruption {
+    BitFlip,
+    ByteOverwrite,
+    Burst,
+}
 
 #[derive(Parser)]
 struct Args {
@@ -15,9 +23,71 @@ struct Args {
     #[arg(long, default_value_t = 0)]
     drop: u32,
 
+    /// Probability that any packet on the link gets lost
+    #[arg(long, default_value_t = 0.0, value_parser = parse_probability)]
+    loss: f64,
+
+    #[arg(
+        long,
+        value_parser = parse_gilbert_elliott,
+        help = "Bursty Gilbert-Elliott loss on the link.",
+        long_help = "Bursty Gilbert-Elliott loss on the link.\n\
+                        Format: p_good_to_bad,p_bad_to_good,loss_good,loss_bad\n\
+                        Example:\n\
+                        \t--gilbert-elliott 0.05,0.3,0.0,0.8"
+    )]
+    gilbert_elliott: Option<GilbertElliott>,
+
+    /// Seed for the link's random impairments
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
     /// The packet number on the link that get corrupted
     #[arg(long, default_value_t = 0)]
     tamper: u32,
+
+    /// How a tampered packet gets corrupted
+    #[arg(long, value_enum, default_value_t = Corruption::BitFlip)]
+    corruption: Corruption,
+
+    /// Number of consecutive bits flipped by burst corruption
+    #[arg(long, default_value_t = 8)]
+    burst_bits: usize,
+
+    /// Probability that any payload bit on the link gets flipped
+    #[arg(long, default_value_t = 0.0, value_parser = parse_probability)]
+    ber: f64,
+
+    /// Number of packets each node sends to the other
+    #[arg(long, default_value_t = 1)]
+    packets: u32,
+}
+
+fn parse_probability(s: &str) -> Result<f64, String> {
+    let p = s
+        .trim()
+        .parse::<f64>()
+        .map_err(|_| format!("Invalid probability: {s}"))?;
+    if !(0.0..=1.0).contains(&p) {
+        return Err(format!("Probability must be within [0, 1]: {s}"));
+    }
+    Ok(p)
+}
+
+fn parse_gilbert_elliott(s: &str) -> Result<GilbertElliott, String> {
+    let probs = s
+        .split(',')
+        .map(parse_probability)
+        .collect::<Result<Vec<f64>, String>>()?;
+    let [p_good_to_bad, p_bad_to_good, loss_good, loss_bad] = probs[..] else {
+        return Err("Expected p_good_to_bad,p_bad_to_good,loss_good,loss_bad".into());
+    };
+    Ok(GilbertElliott {
+        p_good_to_bad,
+        p_bad_to_good,
+        loss_good,
+        loss_bad,
+    })
 }
 
 fn run(args: &Args) {
@@ -31,19 +101,49 @@ fn run(args: &Args) {
         SimpleNode::new(1, &mac, 20)
     };
 
+    let corruption = match args.corruption {
+        Corruption::BitFlip => CorruptionModel::BitFlip,
+        Corruption::ByteOverwrite => CorruptionModel::ByteOverwrite,
+        Corruption::Burst => CorruptionModel::Burst { bits: args.burst_bits },
+    };
+
     let link = {
-        let config = LinkConfig::new(n0.id, n0.port, n1.id, n1.port)
+        let mut config = LinkConfig::new(n0.id, n0.port, n1.id, n1.port)
             .with_delay(args.delay)
             .with_drop(args.drop)
-            .with_corrupt(args.tamper);
+            .with_seed(args.seed)
+            .with_corrupt(args.tamper)
+            .with_corruption(corruption);
+        if args.loss > 0.0 {
+            config = config.with_loss(args.loss);
+        }
+        if args.ber > 0.0 {
+            config = config.with_bit_error_rate(args.ber);
+        }
+        if let Some(ge) = args.gilbert_elliott {
+            config = config.with_gilbert_elliott(ge);
+        }
         Link::new(config)
     };
 
     let p2p = P2PConnection::with_link(&n0, &n1, &link);
     let mut sim = Simulator::new(p2p);
-    sim.schedule_send(1, &n0, n0.port, &n1.mac);
-    sim.schedule_send(2, &n1, n1.port, &n0.mac);
+    for i in 0..args.packets as SimTime {
+        sim.schedule_send(2 * i + 1, &n0, n0.port, &n1.mac);
+        sim.schedule_send(2 * i + 2, &n1, n1.port, &n0.mac);
+    }
     sim.run(); 
+
+    for end in [n0.get_link_id(), n1.get_link_id()] {
+        let link = sim.topology().get_link(end).expect("P2P link must exist");
+        info!(
+            node = %end.0,
+            sent = link.packet_count(),
+            dropped = link.dropped(),
+            corrupted = link.corrupted(),
+            "Link stats from"
+        );
+    }
 }
 
 fn main() {
@@ -1,9 +1,10 @@
 use clap::Parser;
 use pq_macsec::{
     init_logging,
+    link::PortId,
     nodes::simple::SimpleNode,
     packet::MacAddress,
-    simulator::{Simulator, topology::star::StarTopology},
+    simulator::{SimTime, Simulator, topology::star::StarTopology},
 };
 
 #[derive(Parser)]
@@ -39,6 +40,14 @@ struct Args {
     /// Number of nodes in the chain
     #[arg(long, default_value_t = 3)]
     nodes: usize,
+
+    /// Time at which the switch fails
+    #[arg(long)]
+    switch_down_at: Option<SimTime>,
+
+    /// Time at which the failed switch comes back up
+    #[arg(long)]
+    switch_up_at: Option<SimTime>,
 }
 
 fn parse_mac(s: &str) -> Result<MacAddress, String> {
@@ -86,12 +95,19 @@ fn run(args: &Args) {
     let nodes = args.nodes as u8;
     let switch_mac = args.switch_mac;
     let node_macs = generate_macs(&args.node_mac, nodes - 1);
-    let switch_ports = (1..nodes).collect();
+    let switch_ports: Vec<PortId> = (1..nodes).collect();
     let star = StarTopology::new(nodes)
         .add_switch(&switch_mac, &switch_ports)
         .add_nodes(&node_macs)
         .add_links();
+    let switch_id = star.switch_id();
     let mut sim = Simulator::new(star);
+    if let Some(time) = args.switch_down_at {
+        sim.schedule_node_down(time, switch_id);
+    }
+    if let Some(time) = args.switch_up_at {
+        sim.schedule_node_up(time, switch_id);
+    }
     let n = SimpleNode::new(1, &node_macs[0], 0);
     for i in 2..nodes {
         let idx = (i - 1) as usize;
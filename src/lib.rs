@@ -2,6 +2,9 @@ pub mod link;
 pub mod simulator;
 pub mod nodes;
 pub mod packet;
+pub mod secret;
+#[cfg(test)]
+mod test_utils;
 
 pub fn init_logging() {
     tracing_subscriber::fmt()
@@ -1,28 +1,83 @@
-use crate::{link::config::LinkConfig, nodes::NodeId, packet::EthernetFrame, simulator::SimTime};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    assert_or_log,
+    link::config::{CorruptionModel, LinkConfig},
+    log_frame,
+    nodes::NodeId,
+    packet::EthernetFrame,
+    simulator::SimTime,
+};
 pub mod config;
 pub type PortId = u8;
 
 pub type LinkEndId = (NodeId, PortId);
 
+// Each random impairment gets its own ChaCha stream, so frames taken by
+// one never shift the draws seen by another.
+const LOSS_STREAM: u64 = 0;
+const BURST_STREAM: u64 = 1;
+const CORRUPT_STREAM: u64 = 2;
+
+fn impairment_rng(seed: u64, (node, port): LinkEndId, impairment: u64) -> ChaCha8Rng {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    rng.set_stream(((node as u64) << 16) | ((port as u64) << 8) | impairment);
+    rng
+}
+
 #[derive(Clone)]
 pub struct Link {
     pub config: LinkConfig,
     packet_count: u32,
+    dropped: u32,
+    corrupted: u32,
+    in_bad_state: bool,
+    loss_rng: ChaCha8Rng,
+    burst_rng: ChaCha8Rng,
+    corrupt_rng: ChaCha8Rng,
 }
 
 impl Link {
     pub fn new(config: LinkConfig) -> Self {
+        if let Some(p) = config.loss_probability {
+            assert_or_log!(
+                (0.0..=1.0).contains(&p),
+                loss_probability = %p,
+                "Link loss probability must be within [0, 1]",
+            );
+        }
+        if let Some(ber) = config.bit_error_rate {
+            assert_or_log!(
+                (0.0..=1.0).contains(&ber),
+                bit_error_rate = %ber,
+                "Link bit error rate must be within [0, 1]",
+            );
+        }
+        if let Some(ge) = config.gilbert_elliott {
+            let probs = [ge.p_good_to_bad, ge.p_bad_to_good, ge.loss_good, ge.loss_bad];
+            assert_or_log!(
+                probs.iter().all(|p| (0.0..=1.0).contains(p)),
+                gilbert_elliott = ?ge,
+                "Gilbert-Elliott probabilities must be within [0, 1]",
+            );
+        }
+        let seed = config.seed.unwrap_or(0);
+        let end = config.end_a;
         Self {
             config,
             packet_count: 0,
+            dropped: 0,
+            corrupted: 0,
+            in_bad_state: false,
+            loss_rng: impairment_rng(seed, end, LOSS_STREAM),
+            burst_rng: impairment_rng(seed, end, BURST_STREAM),
+            corrupt_rng: impairment_rng(seed, end, CORRUPT_STREAM),
         }
     }
 
     pub fn swap_ends(&self) -> Self {
-        Self {
-            config: self.config.swap_ends(),
-            ..*self
-        }
+        Self::new(self.config.swap_ends())
     }
 
     pub fn get_peer(&self, end: LinkEndId) -> LinkEndId {
@@ -37,23 +92,145 @@ impl Link {
         (self.config.end_a, self.config.end_b)
     }
 
+    pub fn packet_count(&self) -> u32 {
+        self.packet_count
+    }
+
+    pub fn dropped(&self) -> u32 {
+        self.dropped
+    }
+
+    pub fn corrupted(&self) -> u32 {
+        self.corrupted
+    }
+
+    fn gilbert_elliott_loss(&mut self) -> bool {
+        let Some(ge) = self.config.gilbert_elliott else {
+            return false;
+        };
+        let (loss, switch) = if self.in_bad_state {
+            (ge.loss_bad, ge.p_bad_to_good)
+        } else {
+            (ge.loss_good, ge.p_good_to_bad)
+        };
+        let lost = self.burst_rng.random_bool(loss);
+        if self.burst_rng.random_bool(switch) {
+            self.in_bad_state = !self.in_bad_state;
+        }
+        lost
+    }
+
+    fn should_drop(&mut self) -> bool {
+        let nth = self
+            .config
+            .drop_every
+            .is_some_and(|n| n > 0 && self.packet_count.is_multiple_of(n));
+
+        // Random loss draws on every frame, even ones `drop_every` already
+        // takes, so adding deterministic drops doesn't reshuffle it.
+        let uniform = match self.config.loss_probability {
+            Some(p) => self.loss_rng.random_bool(p),
+            None => false,
+        };
+        let bursty = self.gilbert_elliott_loss();
+
+        nth || uniform || bursty
+    }
+
+    fn flip_bit(payload: &mut [u8], bit: usize) {
+        payload[bit / 8] ^= 0x80 >> (bit % 8);
+    }
+
+    fn apply_corruption(&mut self, payload: &mut [u8], model: CorruptionModel) {
+        let bits = payload.len() * 8;
+        match model {
+            CorruptionModel::BitFlip => {
+                let bit = self.corrupt_rng.random_range(0..bits);
+                Self::flip_bit(payload, bit);
+            }
+            CorruptionModel::ByteOverwrite => {
+                let idx = self.corrupt_rng.random_range(0..payload.len());
+                payload[idx] ^= self.corrupt_rng.random_range(1..=u8::MAX);
+            }
+            CorruptionModel::Burst { bits: len } => {
+                let len = len.clamp(1, bits);
+                let start = self.corrupt_rng.random_range(0..=bits - len);
+                for bit in start..start + len {
+                    Self::flip_bit(payload, bit);
+                }
+            }
+        }
+    }
+
+    /// Flips each payload bit independently with probability `ber`. Rather
+    /// than a coin per bit, the gap to the next flipped bit is drawn from
+    /// a geometric distribution.
+    fn apply_bit_errors(&mut self, payload: &mut [u8], ber: f64) -> bool {
+        if ber == 0.0 {
+            return false;
+        }
+
+        let bits = payload.len() * 8;
+        // ln_1p keeps tiny rates from rounding ln(1 - ber) down to 0.
+        let log_keep = (-ber).ln_1p();
+        let mut bit: usize = 0;
+        let mut flipped = false;
+        loop {
+            let u: f64 = self.corrupt_rng.random();
+            // ln(1 - u) / ln(1 - ber) is 0 when ber == 1, so every bit flips.
+            let gap = ((-u).ln_1p() / log_keep).floor() as usize;
+            bit = bit.saturating_add(gap);
+            if bit >= bits {
+                return flipped;
+            }
+            Self::flip_bit(payload, bit);
+            flipped = true;
+            bit += 1;
+        }
+    }
+
+    /// Damages the payload in place and reports whether anything changed.
+    fn corrupt(&mut self, payload: &mut [u8]) -> bool {
+        if payload.is_empty() {
+            return false;
+        }
+
+        let mut corrupted = false;
+        if let Some(corrupt_every) = self.config.corrupt_every
+            && corrupt_every > 0
+            && self.packet_count.is_multiple_of(corrupt_every)
+        {
+            let model = self.config.corruption.unwrap_or_default();
+            self.apply_corruption(payload, model);
+            corrupted = true;
+        }
+
+        if let Some(ber) = self.config.bit_error_rate {
+            corrupted |= self.apply_bit_errors(payload, ber);
+        }
+        corrupted
+    }
+
     pub fn handle_pkt(
         &mut self,
-        pkt: EthernetFrame,
+        mut pkt: EthernetFrame,
         current_time: SimTime,
     ) -> (Option<EthernetFrame>, SimTime) {
         self.packet_count = self.packet_count.wrapping_add(1);
 
-        if let Some(corrupt_every) = self.config.corrupt_every {
-            if corrupt_every > 0 && self.packet_count % corrupt_every == 0 {
-                todo!("Need to add corruption logic");
-            }
+        let (node, port) = self.config.end_a;
+        if self.should_drop() {
+            // Corruption draws for lost frames too, so losses never shift
+            // which bits get flipped on the frames that follow.
+            self.corrupt(&mut pkt.payload.clone());
+            self.dropped = self.dropped.wrapping_add(1);
+            log_frame!("DROP", current_time, pkt, node, port);
+            return (None, current_time);
         }
 
-        if let Some(drop_every) = self.config.drop_every {
-            if drop_every > 0 && self.packet_count % drop_every == 0 {
-                todo!("Need to add drop logic");
-            }
+        if self.corrupt(&mut pkt.payload) {
+            self.corrupted = self.corrupted.wrapping_add(1);
+            log_frame!("CORRUPT", current_time, pkt, node, port);
         }
 
         let delivery_time = if let Some(delay) = self.config.delay {
@@ -77,3 +254,200 @@ impl LinkBuilder {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::frame;
+    use crate::link::config::GilbertElliott;
+
+    /// Sends `count` frames through the link, returning which ones were lost.
+    fn run(link: &mut Link, count: u64) -> Vec<bool> {
+        (0..count)
+            .map(|t| link.handle_pkt(frame(), t).0.is_none())
+            .collect()
+    }
+
+    #[test]
+    fn drop_every_drops_each_nth_frame() {
+        let mut link = Link::new(LinkConfig::new(0, 10, 1, 20).with_drop(3));
+        let lost = run(&mut link, 7);
+        let expected = [false, false, true, false, false, true, false];
+        assert_eq!(lost, expected);
+        assert_eq!(link.dropped(), 2);
+        assert_eq!(link.packet_count(), 7);
+    }
+
+    #[test]
+    fn same_seed_gives_same_losses() {
+        let cfg = LinkConfig::new(0, 10, 1, 20).with_loss(0.5).with_seed(42);
+        let mut a = Link::new(cfg.clone());
+        let mut b = Link::new(cfg);
+        let lost = run(&mut a, 64);
+        assert_eq!(lost, run(&mut b, 64));
+        assert!(lost.contains(&true) && lost.contains(&false));
+    }
+
+    /// Sends one frame through a link that corrupts every frame with
+    /// `model`, returning the positions of the flipped payload bits.
+    fn flipped_bits(model: CorruptionModel, seed: u64) -> Vec<usize> {
+        let cfg = LinkConfig::new(0, 10, 1, 20)
+            .with_corrupt(1)
+            .with_corruption(model)
+            .with_seed(seed);
+        let mut link = Link::new(cfg);
+        let sent = frame();
+        let (got, _) = link.handle_pkt(sent.clone(), 0);
+        let got = got.unwrap();
+        (0..sent.payload.len() * 8)
+            .filter(|bit| (sent.payload[bit / 8] ^ got.payload[bit / 8]) & (0x80 >> (bit % 8)) != 0)
+            .collect()
+    }
+
+    #[test]
+    fn bit_flip_changes_one_bit() {
+        for seed in 0..32 {
+            assert_eq!(flipped_bits(CorruptionModel::BitFlip, seed).len(), 1);
+        }
+    }
+
+    #[test]
+    fn byte_overwrite_changes_one_byte() {
+        for seed in 0..32 {
+            let mut bytes = flipped_bits(CorruptionModel::ByteOverwrite, seed)
+                .into_iter()
+                .map(|bit| bit / 8)
+                .collect::<Vec<_>>();
+            bytes.dedup();
+            assert_eq!(bytes.len(), 1);
+        }
+    }
+
+    #[test]
+    fn burst_flips_consecutive_bits() {
+        for seed in 0..32 {
+            let flipped = flipped_bits(CorruptionModel::Burst { bits: 12 }, seed);
+            assert_eq!(flipped.len(), 12);
+            assert_eq!(flipped[11] - flipped[0], 11);
+        }
+        assert_eq!(flipped_bits(CorruptionModel::Burst { bits: 0 }, 0).len(), 1);
+    }
+
+    #[test]
+    fn corrupted_counts_damaged_frames() {
+        let mut link = Link::new(LinkConfig::new(0, 10, 1, 20).with_corrupt(2));
+        run(&mut link, 6);
+        assert_eq!(link.corrupted(), 3);
+        assert_eq!(link.dropped(), 0);
+    }
+
+    #[test]
+    fn zero_bit_error_rate_leaves_frames_intact() {
+        let mut link = Link::new(LinkConfig::new(0, 10, 1, 20).with_bit_error_rate(0.0));
+        for t in 0..16 {
+            let (got, _) = link.handle_pkt(frame(), t);
+            assert_eq!(got.unwrap().payload, frame().payload);
+        }
+        assert_eq!(link.corrupted(), 0);
+    }
+
+    #[test]
+    fn tiny_bit_error_rate_leaves_frames_intact() {
+        let mut link = Link::new(LinkConfig::new(0, 10, 1, 20).with_bit_error_rate(1e-18));
+        for t in 0..16 {
+            let (got, _) = link.handle_pkt(frame(), t);
+            assert_eq!(got.unwrap().payload, frame().payload);
+        }
+        assert_eq!(link.corrupted(), 0);
+    }
+
+    #[test]
+    fn full_bit_error_rate_flips_every_bit() {
+        let mut link = Link::new(LinkConfig::new(0, 10, 1, 20).with_bit_error_rate(1.0));
+        let (got, _) = link.handle_pkt(frame(), 0);
+        let inverted: Vec<u8> = frame().payload.iter().map(|b| !b).collect();
+        assert_eq!(got.unwrap().payload, inverted);
+    }
+
+    #[test]
+    fn drop_every_keeps_random_impairments() {
+        let ge = GilbertElliott {
+            p_good_to_bad: 0.1,
+            p_bad_to_good: 0.3,
+            loss_good: 0.05,
+            loss_bad: 0.8,
+        };
+        let cfg = LinkConfig::new(0, 10, 1, 20)
+            .with_loss(0.3)
+            .with_gilbert_elliott(ge)
+            .with_bit_error_rate(0.01)
+            .with_seed(1);
+        let mut random_only = Link::new(cfg.clone());
+        let mut with_nth = Link::new(cfg.with_drop(7));
+
+        for i in 1..=200u64 {
+            let (a, _) = random_only.handle_pkt(frame(), i);
+            let (b, _) = with_nth.handle_pkt(frame(), i);
+            let nth = i % 7 == 0;
+            assert_eq!(b.is_none(), a.is_none() || nth, "frame {i} loss diverged");
+            if let (Some(a), Some(b)) = (a, b) {
+                assert_eq!(a.payload, b.payload, "frame {i} corruption diverged");
+            }
+        }
+        assert!(random_only.corrupted() > 0);
+    }
+
+    fn gilbert_elliott_link(ge: GilbertElliott, seed: u64) -> Link {
+        Link::new(LinkConfig::new(0, 10, 1, 20).with_gilbert_elliott(ge).with_seed(seed))
+    }
+
+    #[test]
+    fn gilbert_elliott_stuck_in_bad_state_loses_everything() {
+        let ge = GilbertElliott {
+            p_good_to_bad: 1.0,
+            p_bad_to_good: 0.0,
+            loss_good: 0.0,
+            loss_bad: 1.0,
+        };
+        let lost = run(&mut gilbert_elliott_link(ge, 0), 20);
+        assert!(!lost[0]);
+        assert!(lost[1..].iter().all(|&l| l));
+    }
+
+    #[test]
+    fn gilbert_elliott_is_reproducible() {
+        let ge = GilbertElliott {
+            p_good_to_bad: 0.1,
+            p_bad_to_good: 0.3,
+            loss_good: 0.01,
+            loss_bad: 0.7,
+        };
+        let lost = run(&mut gilbert_elliott_link(ge, 9), 500);
+        assert_eq!(lost, run(&mut gilbert_elliott_link(ge, 9), 500));
+        assert_ne!(lost, run(&mut gilbert_elliott_link(ge, 10), 500));
+    }
+
+    #[test]
+    fn gilbert_elliott_losses_come_in_runs() {
+        // The bad state lasts 1 / p_bad_to_good = 5 frames on average and
+        // loses every frame, so loss runs average well above 1. Uniform
+        // loss at the same overall rate (~1/6) would average 1.2.
+        let ge = GilbertElliott {
+            p_good_to_bad: 0.04,
+            p_bad_to_good: 0.2,
+            loss_good: 0.0,
+            loss_bad: 1.0,
+        };
+        let lost = run(&mut gilbert_elliott_link(ge, 3), 5000);
+        let losses = lost.iter().filter(|&&l| l).count();
+        let runs = lost.windows(2).filter(|w| w[1] && !w[0]).count() + lost[0] as usize;
+        let mean_run = losses as f64 / runs as f64;
+        assert!(mean_run > 3.0, "mean loss run {mean_run}");
+    }
+
+    #[test]
+    fn directions_use_different_streams() {
+        let mut link = Link::new(LinkConfig::new(0, 10, 1, 20).with_loss(0.5).with_seed(42));
+        let mut rev_link = link.swap_ends();
+        assert_ne!(run(&mut link, 64), run(&mut rev_link, 64));
+    }
+}
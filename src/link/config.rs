@@ -5,13 +5,48 @@ use crate::{
     nodes::NodeId,
 };
 
+/// How a frame selected by `corrupt_every` gets damaged. Only the payload
+/// is touched, the simulator has no FCS to catch it.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(tag = "kind")]
+pub enum CorruptionModel {
+    #[default]
+    #[serde(rename = "bit_flip")]
+    BitFlip,
+
+    #[serde(rename = "byte_overwrite")]
+    ByteOverwrite,
+
+    #[serde(rename = "burst")]
+    Burst { bits: usize },
+}
+
+/// Two-state bursty loss model. Each frame is lost with the loss rate of
+/// the current state, after which the link may switch state.
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct GilbertElliott {
+    pub p_good_to_bad: f64,
+    pub p_bad_to_good: f64,
+    pub loss_good: f64,
+    pub loss_bad: f64,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct LinkConfig {
     pub end_a: LinkEndId,
     pub end_b: LinkEndId,
     pub delay: Option<u32>,
     pub corrupt_every: Option<u32>,
+    pub corruption: Option<CorruptionModel>,
+    /// Probability that any payload bit is flipped, applied to every frame.
+    pub bit_error_rate: Option<f64>,
     pub drop_every: Option<u32>,
+    /// Probability in `[0, 1]` that any frame is lost on the link.
+    pub loss_probability: Option<f64>,
+    pub gilbert_elliott: Option<GilbertElliott>,
+    /// Seed for the link's random impairments. Both directions of a link
+    /// share it but draw from independent streams.
+    pub seed: Option<u64>,
 }
 
 impl LinkConfig {
@@ -23,7 +58,12 @@ impl LinkConfig {
             end_b,
             delay: None,
             corrupt_every: None,
+            corruption: None,
+            bit_error_rate: None,
             drop_every: None,
+            loss_probability: None,
+            gilbert_elliott: None,
+            seed: None,
         }
     }
 
@@ -37,11 +77,36 @@ impl LinkConfig {
         self
     }
 
+    pub fn with_corruption(mut self, corruption: CorruptionModel) -> Self {
+        self.corruption = Some(corruption);
+        self
+    }
+
+    pub fn with_bit_error_rate(mut self, bit_error_rate: f64) -> Self {
+        self.bit_error_rate = Some(bit_error_rate);
+        self
+    }
+
     pub fn with_drop(mut self, drop_every: u32) -> Self {
         self.drop_every = Some(drop_every);
         self
     }
 
+    pub fn with_loss(mut self, loss_probability: f64) -> Self {
+        self.loss_probability = Some(loss_probability);
+        self
+    }
+
+    pub fn with_gilbert_elliott(mut self, gilbert_elliott: GilbertElliott) -> Self {
+        self.gilbert_elliott = Some(gilbert_elliott);
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
     pub fn swap_ends(&self) -> Self {
         Self {
             end_a: self.end_b,
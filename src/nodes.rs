@@ -87,13 +87,13 @@ impl NodeBuilder {
     pub fn build(cfg: &NodeConfig) -> Node {
         match cfg {
             NodeConfig::Simple { id, port, mac } => {
-                Node::Simple(SimpleNode::new(*id, &mac, *port))
+                Node::Simple(SimpleNode::new(*id, mac, *port))
             },
             NodeConfig::Forwarding { id, ports, mac } => {
-                Node::Forwarding(ForwardingNode::new(*id, &ports, &mac))
+                Node::Forwarding(ForwardingNode::new(*id, ports, mac))
             }
             NodeConfig::Switch { id, ports, mac } => {
-                Node::Switch(SwitchingNode::new(*id, &ports, &mac))
+                Node::Switch(SwitchingNode::new(*id, ports, mac))
             }
         }
 
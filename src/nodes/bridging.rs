@@ -63,7 +63,7 @@ impl NodeHandler for ForwardingNode {
             port = %out_port,
             "Node cannot send pkts out of port. It doesn't exist",
         );
-        let frame = EthernetFrame::new(self.mac, dst_mac.clone(), 0x800, b"Hello".to_vec());
+        let frame = EthernetFrame::new(self.mac, *dst_mac, 0x800, b"Hello".to_vec());
         let from  = (self.id, out_port);
         Some(NodeAction::Send {
             from,
@@ -25,7 +25,7 @@ impl SimpleNode {
 
 impl NodeHandler for SimpleNode {
     fn send_pkt(&self, dst_mac: &MacAddress, out_port: PortId) -> Option<NodeAction> {
-        let frame = EthernetFrame::new(self.mac, dst_mac.clone(), 0x800, b"Hello".to_vec());
+        let frame = EthernetFrame::new(self.mac, *dst_mac, 0x800, b"Hello".to_vec());
         let from  = (self.id, out_port);
         Some(NodeAction::Send {
             from,
@@ -18,11 +18,11 @@ pub struct SwitchingNode {
 }
 
 impl SwitchingNode {
-    pub fn new(id: NodeId, ports: &Vec<PortId>, mac: &MacAddress) -> Self {
+    pub fn new(id: NodeId, ports: &[PortId], mac: &MacAddress) -> Self {
         Self {
             id,
             ports: ports.iter().cloned().collect(),
-            mac: mac.clone(),
+            mac: *mac,
             mac_address_table: HashMap::new(),
         }
     }
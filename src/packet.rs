@@ -1,5 +1,7 @@
 pub type MacAddress = [u8; 6];
 
+pub const ETHERTYPE_MACSEC: u16 = 0x88e5;
+
 #[derive(Debug, Clone)]
 pub struct EthernetFrame {
     pub src_mac: MacAddress,
@@ -22,4 +24,10 @@ impl EthernetFrame {
             payload,
         }
     }
+
+    /// MACsec protected frames carry ciphertext, which should never be
+    /// printed as if it were plain data.
+    pub fn is_protected(&self) -> bool {
+        self.ethertype == ETHERTYPE_MACSEC
+    }
 }
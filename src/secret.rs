@@ -0,0 +1,127 @@
+use std::fmt;
+
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+macro_rules! secret_key {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        pub struct $name(Vec<u8>);
+
+        impl $name {
+            pub fn new(bytes: Vec<u8>) -> Self {
+                Self(bytes)
+            }
+
+            pub fn expose(&self) -> &[u8] {
+                &self.0
+            }
+
+            pub fn len(&self) -> usize {
+                self.0.len()
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.0.is_empty()
+            }
+        }
+
+        impl Zeroize for $name {
+            fn zeroize(&mut self) {
+                self.0.zeroize();
+            }
+        }
+
+        impl Drop for $name {
+            fn drop(&mut self) {
+                self.zeroize();
+            }
+        }
+
+        impl ZeroizeOnDrop for $name {}
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}(<redacted {} bytes>)", stringify!($name), self.0.len())
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("<redacted>")
+            }
+        }
+    };
+}
+
+secret_key!(
+    /// Secure Connectivity Association Key.
+    Cak
+);
+secret_key!(
+    /// Key Encrypting Key derived from the CAK, used to wrap SAKs.
+    Kek
+);
+secret_key!(
+    /// Integrity Check Value Key derived from the CAK, used to protect MKPDUs.
+    Ick
+);
+secret_key!(
+    /// Secure Association Key used by the SecY to protect frames.
+    Sak
+);
+secret_key!(
+    /// Secret half of a post-quantum KEM or signature key pair.
+    PqSecretKey
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+
+    // Compile-time check only: the marker says nothing about what `Drop`
+    // does, that is covered by `zeroize_wipes_key_bytes`.
+    #[test]
+    fn secret_types_implement_zeroize_on_drop() {
+        assert_zeroize_on_drop::<Cak>();
+        assert_zeroize_on_drop::<Kek>();
+        assert_zeroize_on_drop::<Ick>();
+        assert_zeroize_on_drop::<Sak>();
+        assert_zeroize_on_drop::<PqSecretKey>();
+    }
+
+    #[test]
+    fn zeroize_wipes_key_bytes() {
+        let mut key = Kek::new(vec![0xab; 32]);
+        key.zeroize();
+        assert!(key.is_empty());
+
+        let buf = &key.0;
+        // SAFETY: the Vec still owns its allocation, and zeroizing a Vec
+        // writes zeros to its whole capacity before clearing it.
+        let bytes = unsafe { std::slice::from_raw_parts(buf.as_ptr(), buf.capacity()) };
+        assert!(bytes.len() >= 32);
+        assert!(bytes.iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn debug_and_display_hide_key_bytes() {
+        let key = Sak::new(vec![0xab; 16]);
+        let debug = format!("{key:?}");
+        let display = format!("{key}");
+        assert_eq!(debug, "Sak(<redacted 16 bytes>)");
+        assert_eq!(display, "<redacted>");
+        for out in [&debug, &display] {
+            assert!(!out.to_lowercase().contains("ab"));
+            assert!(!out.contains("171"));
+        }
+    }
+
+    #[test]
+    fn expose_returns_key_bytes() {
+        let key = Cak::new(vec![1, 2, 3]);
+        assert_eq!(key.expose(), &[1, 2, 3]);
+        assert_eq!(key.len(), 3);
+    }
+}
@@ -1,17 +1,19 @@
 use crate::{
     link::PortId,
-    nodes::NodeHandler,
+    nodes::{NodeHandler, NodeId},
     packet::MacAddress,
-    simulator::{event::EventHandler, topology::Topology},
+    simulator::{event::EventHandler, failure::FailureInjector, topology::Topology},
 };
 
 pub mod event;
+pub mod failure;
 pub mod macros;
 pub mod topology;
 pub type SimTime = u64;
 
 pub struct Simulator<T> {
     event_handler: EventHandler,
+    failures: FailureInjector,
     topology: T,
 }
 
@@ -19,10 +21,19 @@ impl<T: Topology> Simulator<T> {
     pub fn new(topology: T) -> Self {
         Self {
             event_handler: EventHandler::new(),
+            failures: FailureInjector::new(),
             topology,
         }
     }
 
+    pub fn topology(&self) -> &T {
+        &self.topology
+    }
+
+    pub fn failures(&self) -> &FailureInjector {
+        &self.failures
+    }
+
     pub fn schedule_send<N: NodeHandler>(
         &mut self,
         time: SimTime,
@@ -33,13 +44,28 @@ impl<T: Topology> Simulator<T> {
         self.event_handler.schedule_send(time, n, port, dst);
     }
 
+    pub fn schedule_node_down(&mut self, time: SimTime, id: NodeId) {
+        self.failures.schedule_down(time, id);
+    }
+
+    pub fn schedule_node_up(&mut self, time: SimTime, id: NodeId) {
+        self.failures.schedule_up(time, id);
+    }
+
     pub fn run(&mut self) {
         while let Some(events) = self.event_handler.next_events() {
+            self.failures.advance(self.event_handler.time);
             for event in events {
+                if self.failures.try_drop(&event) {
+                    continue;
+                }
                 if let Some(next_event) = self.topology.handle_pkt(event) {
                     self.event_handler.schedule(next_event)
                 }
             }
         }
+        // Changes scheduled after the last event still take effect, so
+        // they show up in the trace and in the final node states.
+        self.failures.advance(SimTime::MAX);
     }
 }
@@ -23,6 +23,12 @@ pub struct EventHandler {
     event_queue: BTreeMap<SimTime, Vec<Event>>,
 }
 
+impl Default for EventHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl EventHandler {
     pub fn new() -> Self {
         Self {
@@ -34,7 +40,7 @@ impl EventHandler {
     pub fn schedule(&mut self, event: Event) {
         self.event_queue
             .entry(event.time)
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(event);
     }
 
@@ -0,0 +1,168 @@
+use std::collections::{BTreeMap, HashSet};
+
+use tracing::warn;
+
+use crate::{
+    log_frame,
+    nodes::{NodeAction, NodeId},
+    simulator::{SimTime, event::Event},
+};
+
+#[derive(Clone, Copy)]
+enum NodeState {
+    Down,
+    Up,
+}
+
+/// Tracks scheduled node failures and recoveries. A node that is down
+/// neither sends nor receives, so any frame it would handle is lost.
+#[derive(Default)]
+pub struct FailureInjector {
+    schedule: BTreeMap<SimTime, Vec<(NodeId, NodeState)>>,
+    down_nodes: HashSet<NodeId>,
+    dropped: u32,
+}
+
+impl FailureInjector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn schedule_down(&mut self, time: SimTime, id: NodeId) {
+        self.schedule
+            .entry(time)
+            .or_default()
+            .push((id, NodeState::Down));
+    }
+
+    pub fn schedule_up(&mut self, time: SimTime, id: NodeId) {
+        self.schedule
+            .entry(time)
+            .or_default()
+            .push((id, NodeState::Up));
+    }
+
+    /// Number of frames lost because their node was down.
+    pub fn dropped(&self) -> u32 {
+        self.dropped
+    }
+
+    pub fn is_down(&self, id: NodeId) -> bool {
+        self.down_nodes.contains(&id)
+    }
+
+    /// Applies every state change scheduled at or before `time`, so a
+    /// node failing at `time` already misses the events of that instant.
+    pub fn advance(&mut self, time: SimTime) {
+        while let Some(entry) = self.schedule.first_entry() {
+            if *entry.key() > time {
+                break;
+            }
+            let (at, changes) = entry.remove_entry();
+            for (id, state) in changes {
+                match state {
+                    NodeState::Down => {
+                        warn!(time = at, node = %id, "Node DOWN");
+                        self.down_nodes.insert(id);
+                    }
+                    NodeState::Up => {
+                        warn!(time = at, node = %id, "Node UP");
+                        self.down_nodes.remove(&id);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Drops the event if its node is down, counting and logging the loss.
+    /// Returns whether the event was dropped.
+    pub fn try_drop(&mut self, event: &Event) -> bool {
+        let ((id, port), frame) = match &event.action {
+            NodeAction::Send { from, frame } => (from, frame),
+            NodeAction::Rcv { to, frame } => (to, frame),
+        };
+        if !self.is_down(*id) {
+            return false;
+        }
+        self.dropped = self.dropped.wrapping_add(1);
+        log_frame!("NODE_DOWN", event.time, frame, id, port);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        link::PortId,
+        nodes::simple::SimpleNode,
+        packet::MacAddress,
+        simulator::{Simulator, topology::star::StarTopology},
+        test_utils::frame,
+    };
+
+    fn send(time: SimTime, id: NodeId) -> Event {
+        Event::new(time, NodeAction::Send { from: (id, 0), frame: frame() })
+    }
+
+    fn rcv(time: SimTime, id: NodeId) -> Event {
+        Event::new(time, NodeAction::Rcv { to: (id, 0), frame: frame() })
+    }
+
+    #[test]
+    fn down_node_misses_send_and_rcv_at_failure_time() {
+        let mut failures = FailureInjector::new();
+        failures.schedule_down(5, 1);
+
+        failures.advance(4);
+        assert!(!failures.try_drop(&send(4, 1)));
+
+        failures.advance(5);
+        assert!(failures.try_drop(&send(5, 1)));
+        assert!(failures.try_drop(&rcv(5, 1)));
+        assert!(!failures.try_drop(&send(5, 2)));
+        assert_eq!(failures.dropped(), 2);
+    }
+
+    #[test]
+    fn node_up_restores_delivery() {
+        let mut failures = FailureInjector::new();
+        failures.schedule_down(2, 1);
+        failures.schedule_up(4, 1);
+
+        failures.advance(3);
+        assert!(failures.try_drop(&rcv(3, 1)));
+
+        failures.advance(4);
+        assert!(!failures.is_down(1));
+        assert!(!failures.try_drop(&rcv(4, 1)));
+        assert_eq!(failures.dropped(), 1);
+    }
+
+    #[test]
+    fn star_recovers_after_switch_comes_back() {
+        let switch_mac: MacAddress = [0xd4, 0xa7, 0xc3, 0x2f, 0x4a, 0xbc];
+        let macs: Vec<MacAddress> = (1..=3).map(|i| [0, 1, 0, 2, 0, i]).collect();
+        let ports: Vec<PortId> = vec![1, 2, 3];
+        let star = StarTopology::new(4)
+            .add_switch(&switch_mac, &ports)
+            .add_nodes(&macs)
+            .add_links();
+        let switch_id = star.switch_id();
+        let mut sim = Simulator::new(star);
+        sim.schedule_node_down(2, switch_id);
+        sim.schedule_node_up(4, switch_id);
+
+        let n1 = SimpleNode::new(1, &macs[0], 0);
+        for time in 1..=5 {
+            sim.schedule_send(time, &n1, n1.port, &macs[1]);
+        }
+        sim.run();
+
+        // Frames sent at t=2 and t=3 die at the switch, the rest get through.
+        let to_n2 = sim.topology().get_link((switch_id, 2)).unwrap();
+        assert_eq!(to_n2.packet_count(), 3);
+        assert_eq!(sim.failures().dropped(), 2);
+        assert!(!sim.failures().is_down(switch_id));
+    }
+}
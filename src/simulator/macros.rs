@@ -1,4 +1,4 @@
-use crate::packet::MacAddress;
+use crate::packet::{EthernetFrame, MacAddress};
 
 pub fn format_mac(mac: &MacAddress) -> String {
     format!(
@@ -7,18 +7,42 @@ pub fn format_mac(mac: &MacAddress) -> String {
     )
 }
 
+pub fn format_payload(frame: &EthernetFrame) -> String {
+    if frame.is_protected() {
+        format!("<ciphertext {} bytes>", frame.payload.len())
+    } else {
+        String::from_utf8_lossy(&frame.payload).into_owned()
+    }
+}
+
+/// Logs a frame. The five argument form names the link end by node and
+/// port, for events such as `DROP` that happen on a link rather than at
+/// a node's send or receive.
 #[macro_export]
 macro_rules! log_frame {
+    ($action:expr, $time:expr, $frame:expr, $node:expr, $port:expr) => {
+        let src_mac = $crate::simulator::macros::format_mac(&$frame.src_mac);
+        let dst_mac = $crate::simulator::macros::format_mac(&$frame.dst_mac);
+        let ethertype = format!("0x{:04x}", $frame.ethertype);
+        let payload = $crate::simulator::macros::format_payload(&$frame);
+        tracing::info!(
+            time = $time, node = $node, port = $port, src_mac = src_mac,
+            dst_mac = dst_mac, ethertype = ethertype,
+            payload = payload,
+            $action,
+        );
+    };
     ($action:expr, $time:expr, $frame:expr, $port:expr) => {
         let src_mac = $crate::simulator::macros::format_mac(&$frame.src_mac);
         let dst_mac = $crate::simulator::macros::format_mac(&$frame.dst_mac);
         let ethertype = format!("0x{:04x}", $frame.ethertype);
+        let payload = $crate::simulator::macros::format_payload(&$frame);
         match $action {
             "SEND" => {
                 tracing::info!(
                     time = $time, outgoing_port = $port, src_mac = src_mac,
                     dst_mac = dst_mac, ethertype = ethertype,
-                    payload = String::from_utf8_lossy(&$frame.payload).as_ref(),
+                    payload = payload,
                     $action,
                 );
             },
@@ -26,7 +50,7 @@ macro_rules! log_frame {
                 tracing::info!(
                     time = $time, incoming_port = $port, src_mac = src_mac,
                     dst_mac = dst_mac, ethertype = ethertype,
-                    payload = String::from_utf8_lossy(&$frame.payload).as_ref(),
+                    payload = payload,
                     $action,
                 );
             },
@@ -34,10 +58,79 @@ macro_rules! log_frame {
                 tracing::info!(
                     time = $time, src_mac = src_mac, dst_mac = dst_mac, 
                     ethertype = ethertype,
-                    payload = String::from_utf8_lossy(&$frame.payload).as_ref(),
+                    payload = payload,
                     $action,
                 );
             },
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io,
+        sync::{Arc, Mutex},
+    };
+
+    use crate::{
+        packet::{ETHERTYPE_MACSEC, EthernetFrame},
+        test_utils::frame,
+    };
+
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn capture_logs(f: impl FnOnce()) -> String {
+        let capture = Capture::default();
+        let writer = capture.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .finish();
+        tracing::subscriber::with_default(subscriber, f);
+        String::from_utf8(capture.0.lock().unwrap().clone()).unwrap()
+    }
+
+    fn logged(frame: &EthernetFrame) -> String {
+        capture_logs(|| {
+            log_frame!("SEND", 1, frame, 0);
+        })
+    }
+
+    #[test]
+    fn protected_payload_is_logged_as_length() {
+        let frame = EthernetFrame::new([0; 6], [1; 6], ETHERTYPE_MACSEC, b"Secret".to_vec());
+        let line = logged(&frame);
+        assert!(line.contains("<ciphertext 6 bytes>"));
+        assert!(!line.contains("Secret"));
+    }
+
+    #[test]
+    fn link_events_log_node_and_port() {
+        let frame = frame();
+        let line = capture_logs(|| {
+            log_frame!("DROP", 1, frame, 7, 3);
+        });
+        assert!(line.contains("DROP"));
+        assert!(line.contains("node=7"));
+        assert!(line.contains("port=3"));
+    }
+
+    #[test]
+    fn clear_payload_is_logged_as_text() {
+        let frame = frame();
+        assert!(logged(&frame).contains("Hello"));
+    }
+}
@@ -29,7 +29,7 @@ impl ChainTopology {
         }
     }
 
-    pub fn build(mut self, macs: &Vec<MacAddress>, ports: &Vec<PortId>) -> Self {
+    pub fn build(mut self, macs: &[MacAddress], ports: &[PortId]) -> Self {
         assert_or_log!(
             macs.len() == self.num_nodes as usize,
             "The number of MAC addresses doesn't match number of nodes"
@@ -44,6 +44,10 @@ impl ChainTopology {
         self
     }
 
+    pub fn get_link(&self, end: LinkEndId) -> Option<&Link> {
+        self.links.get(&end)
+    }
+
     fn create_nodes(&mut self, macs: &[MacAddress], ports: &[PortId]) {
         let last = (self.num_nodes - 1) as usize;
         for (i, mac) in macs.iter().enumerate() {
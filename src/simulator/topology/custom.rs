@@ -20,6 +20,12 @@ pub struct CustomTopology {
     links: HashMap<LinkEndId, Link>,
 }
 
+impl Default for CustomTopology {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CustomTopology {
     pub fn new() -> Self {
         Self {
@@ -51,6 +57,10 @@ impl CustomTopology {
         self.nodes.get(&id)
     }
 
+    pub fn get_link(&self, end: LinkEndId) -> Option<&Link> {
+        self.links.get(&end)
+    }
+
     pub fn connect(self) -> Self {
         todo!()
     }
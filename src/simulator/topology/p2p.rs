@@ -1,5 +1,5 @@
 use crate::{
-    link::{Link, PortId, config::LinkConfig},
+    link::{Link, LinkEndId, PortId, config::LinkConfig},
     log_frame,
     nodes::{NodeAction, NodeHandler, NodeId, simple::SimpleNode},
     simulator::{event::Event, topology::Topology},
@@ -49,15 +49,21 @@ impl P2PConnection {
         }
     }
 
-    fn get_link_from_id(&self, id: NodeId, port: PortId) -> Link {
+    fn get_link_from_id(&mut self, id: NodeId, port: PortId) -> &mut Link {
         let id_match = self.node_a.id == id;
         let port_match = self.node_a.port == port;
         match (id_match, port_match) {
-            (true, true) => self.a_to_b.clone(),
-            (false, false) => self.b_to_a.clone(),
+            (true, true) => &mut self.a_to_b,
+            (false, false) => &mut self.b_to_a,
             (_, _) => unreachable!("ID and Port don't match with a single node"),
         }
     }
+
+    pub fn get_link(&self, end: LinkEndId) -> Option<&Link> {
+        [&self.a_to_b, &self.b_to_a]
+            .into_iter()
+            .find(|link| link.config.end_a == end)
+    }
 }
 
 impl Topology for P2PConnection {
@@ -67,7 +73,7 @@ impl Topology for P2PConnection {
             NodeAction::Send { from, frame } => {
                 let (id, port) = from;
                 log_frame!("SEND", time, frame, port);
-                let mut link = self.get_link_from_id(id, port);
+                let link = self.get_link_from_id(id, port);
                 let peer = link.get_peer(from);
                 let (pkt_opt, del_time) = link.handle_pkt(frame.clone(), time);
                 let pkt = pkt_opt?;
@@ -67,18 +67,18 @@ impl StarTopology {
         self
     }
 
-    pub fn add_switch(mut self, mac: &MacAddress, ports: &Vec<PortId>) -> Self {
+    pub fn add_switch(mut self, mac: &MacAddress, ports: &[PortId]) -> Self {
         assert_or_log!(
             ports.len() == (self.num_nodes - 1) as usize,
             "Cannot have more ports than number of nodes in the network",
         );
         let n = SwitchingNode::new(0, ports, mac);
         self.nodes.insert(0, Node::Switch(n));
-        self.switch_ports = ports.clone();
+        self.switch_ports = ports.to_vec();
         self
     }
 
-    pub fn add_nodes(mut self, macs: &Vec<MacAddress>) -> Self {
+    pub fn add_nodes(mut self, macs: &[MacAddress]) -> Self {
         assert_or_log!(
             macs.len() == (self.num_nodes - 1) as usize,
             "Cannot have more than {} including the switch",
@@ -104,7 +104,7 @@ impl StarTopology {
         self
     }
 
-    fn update_switch(&mut self, macs: &Vec<MacAddress>) {
+    fn update_switch(&mut self, macs: &[MacAddress]) {
         let mapping: Vec<(MacAddress, PortId)> = macs
             .iter()
             .cloned()
@@ -126,6 +126,14 @@ impl StarTopology {
     pub fn get_node(&self, id: NodeId) -> Option<&Node> {
         self.nodes.get(&id) 
     } 
+
+    pub fn switch_id(&self) -> NodeId {
+        self.switch_id
+    }
+
+    pub fn get_link(&self, end: LinkEndId) -> Option<&Link> {
+        self.links.get(&end)
+    }
 }
 
 impl Topology for StarTopology {
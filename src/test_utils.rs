@@ -0,0 +1,6 @@
+use crate::packet::EthernetFrame;
+
+/// Plain frame shared by tests that only care about how it travels.
+pub fn frame() -> EthernetFrame {
+    EthernetFrame::new([0; 6], [1; 6], 0x800, b"Hello".to_vec())
+}